@@ -0,0 +1,133 @@
+use std::os::raw::c_int;
+use std::path::Path;
+use ffi::unqlite_config;
+use super::UnQlite;
+
+/// Governs when changes made through `kv_store_typed` are committed to disk,
+/// inspired by the policy of the same name in clementine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncPolicy {
+    /// Commit after every `kv_store_typed` call. Safest, slowest.
+    Always,
+    /// Force `UNQLITE_CONFIG_DISABLE_AUTO_COMMIT` at open time, so nothing
+    /// commits unless the caller calls `commit` explicitly, regardless of
+    /// `OpenBuilder::disable_auto_commit`.
+    Never,
+    /// Leave commits entirely to the user: `kv_store_typed` never commits on
+    /// its own, and auto-commit is whatever `OpenBuilder::disable_auto_commit`
+    /// (or the engine default) says it is.
+    Manual,
+}
+
+impl Default for SyncPolicy {
+    #[inline]
+    fn default() -> SyncPolicy {
+        SyncPolicy::Manual
+    }
+}
+
+/// Builds an `UnQlite` handle with runtime configuration applied via
+/// `unqlite_config` before it is handed back to the caller.
+///
+/// ```ignore
+/// let db = OpenBuilder::new()
+///     .flags(CREATE | READWRITE | NOMUTEX)
+///     .max_page_cache(4096)
+///     .disable_auto_commit(true)
+///     .sync_policy(SyncPolicy::Always)
+///     .open("test.db")
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct OpenBuilder {
+    flags: ::OpenFlags,
+    max_page_cache: Option<c_int>,
+    disable_auto_commit: bool,
+    sync_policy: SyncPolicy,
+}
+
+impl Default for OpenBuilder {
+    #[inline]
+    fn default() -> OpenBuilder {
+        OpenBuilder {
+            flags: ::OpenFlags::default(),
+            max_page_cache: None,
+            disable_auto_commit: false,
+            sync_policy: SyncPolicy::default(),
+        }
+    }
+}
+
+impl OpenBuilder {
+    /// Start a builder with the same defaults as `UnQlite::create`.
+    #[inline]
+    pub fn new() -> OpenBuilder {
+        OpenBuilder::default()
+    }
+
+    /// Set the `OpenFlags` passed to `unqlite_open`.
+    #[inline]
+    pub fn flags(mut self, flags: ::OpenFlags) -> OpenBuilder {
+        self.flags = flags;
+        self
+    }
+
+    /// Set `UNQLITE_CONFIG_MAX_PAGE_CACHE`, the maximum number of pages to
+    /// hold in the page cache.
+    #[inline]
+    pub fn max_page_cache(mut self, pages: u32) -> OpenBuilder {
+        self.max_page_cache = Some(pages as c_int);
+        self
+    }
+
+    /// Toggle `UNQLITE_CONFIG_DISABLE_AUTO_COMMIT`.
+    #[inline]
+    pub fn disable_auto_commit(mut self, disable: bool) -> OpenBuilder {
+        self.disable_auto_commit = disable;
+        self
+    }
+
+    /// Set the durability policy of the opened handle; see `SyncPolicy`.
+    /// `SyncPolicy::Always` commits only after `kv_store_typed`, not after
+    /// a plain `kv_store`.
+    #[inline]
+    pub fn sync_policy(mut self, policy: SyncPolicy) -> OpenBuilder {
+        self.sync_policy = policy;
+        self
+    }
+
+    /// Open the database at `filename` and apply the configured options.
+    pub fn open<P: AsRef<Path>>(self, filename: P) -> ::Result<UnQlite> {
+        let mut db = try!(UnQlite::try_open_with(filename, self.flags));
+        try!(db.apply_config(&self));
+        Ok(db)
+    }
+
+    /// Open an in-memory database and apply the configured options.
+    ///
+    /// Equivalent to `self.open(":mem:")`.
+    #[inline]
+    pub fn open_in_memory(self) -> ::Result<UnQlite> {
+        self.open(":mem:")
+    }
+}
+
+impl UnQlite {
+    fn apply_config(&mut self, builder: &OpenBuilder) -> ::Result<()> {
+        unsafe {
+            if let Some(pages) = builder.max_page_cache {
+                try!(error_or!(unqlite_config(self.as_raw_mut(),
+                                               ::vars::UNQLITE_CONFIG_MAX_PAGE_CACHE,
+                                               pages)));
+            }
+            let disable_auto_commit = builder.disable_auto_commit ||
+                                       builder.sync_policy == SyncPolicy::Never;
+            if disable_auto_commit {
+                try!(error_or!(unqlite_config(self.as_raw_mut(),
+                                               ::vars::UNQLITE_CONFIG_DISABLE_AUTO_COMMIT)));
+            }
+        }
+        self.sync_policy = builder.sync_policy;
+        Ok(())
+    }
+}
@@ -1,10 +1,30 @@
-use std::mem;
+#[macro_use]
+extern crate bitflags;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
 use std::ffi::CString;
+use std::path::Path;
+use std::ptr;
 use ffi::{unqlite_close, unqlite_open};
 
+#[cfg(unix)]
+fn path_to_cstring(path: &Path) -> ::Result<CString> {
+    use std::os::unix::ffi::OsStrExt;
+    Ok(try!(CString::new(path.as_os_str().as_bytes())))
+}
+
+#[cfg(not(unix))]
+fn path_to_cstring(path: &Path) -> ::Result<CString> {
+    Ok(try!(CString::new(path.to_string_lossy().into_owned())))
+}
+
 /// UnQlite database entity.
 pub struct UnQlite {
     db: *mut ::ffi::unqlite,
+    sync_policy: SyncPolicy,
 }
 
 unsafe impl Send for UnQlite {}
@@ -12,7 +32,10 @@ unsafe impl Sync for UnQlite {}
 
 impl Default for UnQlite {
     fn default() -> UnQlite {
-        UnQlite { db: unsafe { mem::uninitialized() } }
+        UnQlite {
+            db: ptr::null_mut(),
+            sync_policy: SyncPolicy::default(),
+        }
     }
 }
 
@@ -21,19 +44,40 @@ impl<'open> UnQlite {
     ///
     /// ```ignore
     /// let _ = UnQlite::open("str");
-    /// let _ = UnQlite::open(String::new());
+    /// let _ = UnQlite::open(Path::new("test.db"), OpenFlags::default());
     /// ```
     #[inline]
-    fn open<P: AsRef<str>>(filename: P, mode: OpenMode) -> ::Result<UnQlite> {
+    fn open<P: AsRef<Path>>(filename: P, flags: OpenFlags) -> ::Result<UnQlite> {
         unsafe {
             let mut unqlite = UnQlite::default();
-            let filename = filename.as_ref();
-            let filename = try!(CString::new(filename));
-            error_or!(unqlite_open(&mut unqlite.db, filename.as_ptr(), mode.into()),
+            let filename = try!(path_to_cstring(filename.as_ref()));
+            error_or!(unqlite_open(&mut unqlite.db, filename.as_ptr(), flags.into()),
                       unqlite)
         }
     }
 
+    /// Create an UnQlite database at `filename` with an arbitrary combination
+    /// of `OpenFlags`.
+    ///
+    /// The named constructors below (`create`, `open_readonly`, `open_mmap`,
+    /// ...) are thin wrappers around specific flag combinations; reach for
+    /// this one when you need flags they don't expose, e.g.
+    /// `NOMUTEX | OMIT_JOURNALING` for single-threaded bulk loads.
+    ///
+    /// ## Panics
+    ///
+    /// Will panic if failed in opening.
+    #[inline]
+    pub fn open_with<P: AsRef<Path>>(filename: P, flags: OpenFlags) -> UnQlite {
+        Self::try_open_with(filename, flags).unwrap()
+    }
+
+    /// Same as `open_with`, but returns a `Result` instead of panicking.
+    #[inline]
+    pub fn try_open_with<P: AsRef<Path>>(filename: P, flags: OpenFlags) -> ::Result<UnQlite> {
+        Self::open(filename, flags)
+    }
+
     /// Create UnQlite database as `filename`.
     ///
     /// By default, the database is created in read-write mode.
@@ -60,8 +104,14 @@ impl<'open> UnQlite {
     /// rc = unqlite_open(&pDb, ":mem:", UNQLITE_OPEN_MEM);
     /// ```
     #[inline]
-    pub fn create<P: AsRef<str>>(filename: P) -> UnQlite {
-        Self::open(filename, OpenMode::Create).unwrap()
+    pub fn create<P: AsRef<Path>>(filename: P) -> UnQlite {
+        Self::try_create(filename).unwrap()
+    }
+
+    /// Same as `create`, but returns a `Result` instead of panicking.
+    #[inline]
+    pub fn try_create<P: AsRef<Path>>(filename: P) -> ::Result<UnQlite> {
+        Self::open(filename, OpenFlags::default())
     }
 
     /// Create database in memory.
@@ -88,7 +138,13 @@ impl<'open> UnQlite {
     /// ```
     #[inline]
     pub fn create_temp() -> UnQlite {
-        Self::open("", OpenMode::TempDB).unwrap()
+        Self::try_create_temp().unwrap()
+    }
+
+    /// Same as `create_temp`, but returns a `Result` instead of panicking.
+    #[inline]
+    pub fn try_create_temp() -> ::Result<UnQlite> {
+        Self::open("", TEMP_DB)
     }
 
     /// Obtain a read-only memory view of the whole database.
@@ -106,8 +162,14 @@ impl<'open> UnQlite {
     /// unqlite_open(&pDb, "test.db", UNQLITE_OPEN_MMAP | UNQLITE_OPEN_READONLY);
     /// ```
     #[inline]
-    pub fn open_mmap<P: AsRef<str>>(filename: P) -> UnQlite {
-        Self::open(filename, OpenMode::MMap).unwrap()
+    pub fn open_mmap<P: AsRef<Path>>(filename: P) -> UnQlite {
+        Self::try_open_mmap(filename).unwrap()
+    }
+
+    /// Same as `open_mmap`, but returns a `Result` instead of panicking.
+    #[inline]
+    pub fn try_open_mmap<P: AsRef<Path>>(filename: P) -> ::Result<UnQlite> {
+        Self::open(filename, MMAP | READONLY)
     }
 
     /// Open the database in a read-only mode.
@@ -126,8 +188,32 @@ impl<'open> UnQlite {
     /// unqlite_open(&pDb, "test.db", UNQLITE_OPEN_READONLY);
     /// ```
     #[inline]
-    pub fn open_readonly<P: AsRef<str>>(filename: P) -> UnQlite {
-        Self::open(filename, OpenMode::ReadOnly).unwrap()
+    pub fn open_readonly<P: AsRef<Path>>(filename: P) -> UnQlite {
+        Self::try_open_readonly(filename).unwrap()
+    }
+
+    /// Same as `open_readonly`, but returns a `Result` instead of panicking.
+    #[inline]
+    pub fn try_open_readonly<P: AsRef<Path>>(filename: P) -> ::Result<UnQlite> {
+        Self::open(filename, READONLY)
+    }
+
+    /// Start an `OpenBuilder` to tune page cache size, auto-commit and
+    /// durability before opening the database.
+    #[inline]
+    pub fn config() -> OpenBuilder {
+        OpenBuilder::new()
+    }
+
+    /// The durability policy this handle was opened with.
+    #[inline]
+    pub fn sync_policy(&self) -> SyncPolicy {
+        self.sync_policy
+    }
+
+    #[inline]
+    fn as_raw_mut(&mut self) -> *mut ::ffi::unqlite {
+        self.db
     }
 
     fn close(&mut self) -> ::Result<()> {
@@ -137,6 +223,9 @@ impl<'open> UnQlite {
 
 impl Drop for UnQlite {
     fn drop(&mut self) {
+        if self.db.is_null() {
+            return;
+        }
         self.close().unwrap();
     }
 }
@@ -150,7 +239,7 @@ macro_rules! _components {
     }
 }
 
-_components!(openmode, config, util, transaction);
+_components!(openflags, builder, config, util, transaction, value);
 
 #[cfg(test)]
 #[cfg(feature = "enable-threads")]
@@ -175,7 +264,7 @@ mod tests_threadsafe {
 
 #[cfg(test)]
 mod tests {
-    use super::UnQlite;
+    use super::{CREATE, NOMUTEX, READWRITE, OpenBuilder, SyncPolicy, UnQlite};
 
     #[test]
     fn open() {
@@ -183,4 +272,15 @@ mod tests {
         let _ = UnQlite::create_in_memory();
         let _ = UnQlite::open_readonly(":mem:");
     }
+
+    #[test]
+    fn open_builder_configures_handle() {
+        let db = OpenBuilder::new()
+            .flags(CREATE | READWRITE | NOMUTEX)
+            .max_page_cache(64)
+            .sync_policy(SyncPolicy::Always)
+            .open_in_memory()
+            .unwrap();
+        assert_eq!(db.sync_policy(), SyncPolicy::Always);
+    }
 }
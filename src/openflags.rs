@@ -0,0 +1,32 @@
+bitflags! {
+    /// Flags for opening an UnQlite database, passed straight through to
+    /// `unqlite_open`. These mirror the `UNQLITE_OPEN_*` C constants and can
+    /// be combined with `|`, e.g. `CREATE | NOMUTEX | OMIT_JOURNALING`.
+    pub flags OpenFlags: u32 {
+        const READONLY = ::vars::UNQLITE_OPEN_READONLY,
+        const READWRITE = ::vars::UNQLITE_OPEN_READWRITE,
+        const CREATE = ::vars::UNQLITE_OPEN_CREATE,
+        const EXCLUSIVE = ::vars::UNQLITE_OPEN_EXCLUSIVE,
+        const TEMP_DB = ::vars::UNQLITE_OPEN_TEMP_DB,
+        const NOMUTEX = ::vars::UNQLITE_OPEN_NOMUTEX,
+        const OMIT_JOURNALING = ::vars::UNQLITE_OPEN_OMIT_JOURNALING,
+        const IN_MEMORY = ::vars::UNQLITE_OPEN_IN_MEMORY,
+        const MMAP = ::vars::UNQLITE_OPEN_MMAP,
+    }
+}
+
+impl Default for OpenFlags {
+    /// The flags used by `UnQlite::create`: a read-write, on-disk database
+    /// that is created if it does not already exist.
+    #[inline]
+    fn default() -> OpenFlags {
+        CREATE | READWRITE
+    }
+}
+
+impl Into<u32> for OpenFlags {
+    #[inline]
+    fn into(self) -> u32 {
+        self.bits()
+    }
+}
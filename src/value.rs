@@ -0,0 +1,89 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde_json;
+
+use super::{SyncPolicy, UnQlite};
+
+/// Converts a Rust value into the bytes stored for a key/value entry.
+///
+/// A blanket impl is provided for every `Serialize` type behind the `serde`
+/// feature, encoding through JSON; no other encoding is offered.
+pub trait ToValue {
+    fn to_value(&self) -> ::Result<Vec<u8>>;
+}
+
+/// Converts the bytes of a key/value entry back into a Rust value.
+///
+/// A blanket impl is provided for every `DeserializeOwned` type behind the
+/// `serde` feature, decoding through JSON; no other encoding is offered.
+pub trait FromValue: Sized {
+    fn from_value(bytes: &[u8]) -> ::Result<Self>;
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> ToValue for T {
+    #[inline]
+    fn to_value(&self) -> ::Result<Vec<u8>> {
+        Ok(try!(serde_json::to_vec(self)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: DeserializeOwned> FromValue for T {
+    #[inline]
+    fn from_value(bytes: &[u8]) -> ::Result<Self> {
+        Ok(try!(serde_json::from_slice(bytes)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for ::Error {
+    fn from(err: serde_json::Error) -> ::Error {
+        ::Error::Other(Box::new(err))
+    }
+}
+
+impl UnQlite {
+    /// Serialize `value` through its `ToValue` impl and store it at `key`.
+    ///
+    /// With the `serde` feature enabled this accepts any `Serialize` type,
+    /// encoded as JSON. If this handle was opened with
+    /// `SyncPolicy::Always`, the store is followed by a `commit`.
+    #[inline]
+    pub fn kv_store_typed<K: AsRef<[u8]>, V: ToValue>(&self, key: K, value: &V) -> ::Result<()> {
+        let bytes = try!(value.to_value());
+        try!(self.kv_store(key, bytes));
+        if self.sync_policy() == SyncPolicy::Always {
+            try!(self.commit());
+        }
+        Ok(())
+    }
+
+    /// Fetch the value at `key` and deserialize it through its `FromValue`
+    /// impl.
+    ///
+    /// With the `serde` feature enabled this decodes any `DeserializeOwned`
+    /// type from the JSON stored by `kv_store_typed`.
+    #[inline]
+    pub fn kv_fetch_typed<K: AsRef<[u8]>, V: FromValue>(&self, key: K) -> ::Result<V> {
+        let bytes = try!(self.kv_fetch(key));
+        V::from_value(&bytes)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod tests {
+    use super::UnQlite;
+
+    #[test]
+    fn typed_kv_round_trip() {
+        let db = UnQlite::create_in_memory();
+        db.kv_store_typed(b"key", &42i32).unwrap();
+        let value: i32 = db.kv_fetch_typed(b"key").unwrap();
+        assert_eq!(value, 42);
+    }
+}